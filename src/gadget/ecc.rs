@@ -8,44 +8,190 @@ use crate::{
     plonk::Error,
 };
 
+/// Enumerates the fixed bases used in fixed-base scalar multiplication by a
+/// full-width scalar, for a circuit over the given curve. Each variant names
+/// a concrete generator (for example, a note-commitment base or a nullifier
+/// base) whose windowed lookup table the chip loads as a circuit constant.
+pub trait FixedPoints<C: CurveAffine>: Clone + fmt::Debug {}
+
+/// Enumerates the fixed bases used in fixed-base scalar multiplication by a
+/// signed short scalar, for a circuit over the given curve (for example, a
+/// value-commitment base).
+pub trait FixedPointsShort<C: CurveAffine>: Clone + fmt::Debug {}
+
 /// The set of circuit instructions required to use the ECC gadgets.
 pub trait EccInstructions<C: CurveAffine>: Chip<Field = C::Base> {
-    /// Variable representing an element of the elliptic curve's scalar field.
-    type Scalar: Clone + fmt::Debug;
+    /// Variable representing a full-width element of the elliptic curve's
+    /// scalar field, used in fixed-base scalar multiplication.
+    type ScalarFixed: Clone + fmt::Debug;
+    /// Variable representing an element of the elliptic curve's base field, used
+    /// in variable-base scalar multiplication.
+    ///
+    /// A `ScalarVar` cannot represent every element of `C::Scalar`, but is
+    /// sufficient to represent the scalars that arise in variable-base
+    /// multiplication, such as `ivk`, which are guaranteed to be base-field
+    /// elements.
+    type ScalarVar: Clone + fmt::Debug;
+    /// Variable representing a signed scalar used in fixed-base scalar
+    /// multiplication, whose magnitude fits in 64 bits, together with a sign
+    /// of `+1` or `-1`.
+    type ScalarFixedShort: Clone + fmt::Debug;
     /// Variable representing an elliptic curve point.
     type Point: Clone + fmt::Debug;
+    /// The set of fixed bases (named generators) available for fixed-base
+    /// scalar multiplication by a full-width scalar in this circuit.
+    type FixedPoints: FixedPoints<C>;
+    /// The set of fixed bases (named generators) available for fixed-base
+    /// scalar multiplication by a signed short scalar in this circuit.
+    type FixedPointsShort: FixedPointsShort<C>;
     /// Variable representing a fixed elliptic curve point (constant in the circuit).
     type FixedPoint: Clone + fmt::Debug;
+    /// Variable representing a fixed elliptic curve point (constant in the
+    /// circuit) for use in fixed-base scalar multiplication by a signed
+    /// scalar of at most 64 bits in magnitude.
+    type FixedPointShort: Clone + fmt::Debug;
+    /// Variable representing the affine x-coordinate of an elliptic curve point.
+    type X: Clone + fmt::Debug;
+
+    /// Witnesses the given `(x, y)` affine coordinates as a [`Self::Point`],
+    /// constraining it to lie on the curve.
+    fn witness_point(
+        layouter: &mut impl Layouter<Self>,
+        value: Option<C>,
+    ) -> Result<Self::Point, Error>;
+
+    /// Witnesses the given base-field element as a [`Self::ScalarVar`], for use
+    /// in variable-base scalar multiplication.
+    fn witness_scalar_var(
+        layouter: &mut impl Layouter<Self>,
+        value: Option<C::Base>,
+    ) -> Result<Self::ScalarVar, Error>;
+
+    /// Witnesses the given scalar-field element as a [`Self::ScalarFixed`], for
+    /// use in fixed-base scalar multiplication.
+    fn witness_scalar_fixed(
+        layouter: &mut impl Layouter<Self>,
+        value: Option<C::Scalar>,
+    ) -> Result<Self::ScalarFixed, Error>;
 
-    /// Performs point addition, returning `a + b`.
+    /// Witnesses the given scalar-field element, representing a signed value
+    /// in the range `[-(2^64 - 1), 2^64 - 1]`, as a [`Self::ScalarFixedShort`], for use in
+    /// fixed-base scalar multiplication. The value is witnessed as a 64-bit
+    /// unsigned magnitude and a sign bit constrained to `{1, -1}`.
+    fn witness_scalar_fixed_short(
+        layouter: &mut impl Layouter<Self>,
+        value: Option<C::Scalar>,
+    ) -> Result<Self::ScalarFixedShort, Error>;
+
+    /// Performs complete point addition, returning `a + b`. This handles the
+    /// cases where `a` and `b` are equal, are inverses of each other, or
+    /// where either is the point at infinity. The point at infinity is
+    /// represented by the sentinel affine coordinate `(0, 0)`.
     fn add(
         layouter: &mut impl Layouter<Self>,
         a: &Self::Point,
         b: &Self::Point,
     ) -> Result<Self::Point, Error>;
 
+    /// Performs incomplete point addition, returning `a + b`. This is
+    /// cheaper than [`EccInstructions::add`], but is unsound if `a` and `b`
+    /// are equal, are inverses of each other, or if either is the point at
+    /// infinity. Callers must independently prove that these cases cannot
+    /// occur.
+    fn add_incomplete(
+        layouter: &mut impl Layouter<Self>,
+        a: &Self::Point,
+        b: &Self::Point,
+    ) -> Result<Self::Point, Error>;
+
     /// Performs point doubling, returning `[2] a`.
     fn double(layouter: &mut impl Layouter<Self>, a: &Self::Point) -> Result<Self::Point, Error>;
 
     /// Performs variable-base scalar multiplication, returning `[scalar] base`.
     fn mul(
         layouter: &mut impl Layouter<Self>,
-        scalar: &Self::Scalar,
+        scalar: &Self::ScalarVar,
         base: &Self::Point,
     ) -> Result<Self::Point, Error>;
 
     /// Performs fixed-base scalar multiplication, returning `[scalar] base`.
     fn mul_fixed(
         layouter: &mut impl Layouter<Self>,
-        scalar: &Self::Scalar,
+        scalar: &Self::ScalarFixed,
         base: &Self::FixedPoint,
     ) -> Result<Self::Point, Error>;
+
+    /// Performs fixed-base scalar multiplication by a signed short scalar,
+    /// returning `[scalar] base`.
+    fn mul_fixed_short(
+        layouter: &mut impl Layouter<Self>,
+        scalar: &Self::ScalarFixedShort,
+        base: &Self::FixedPointShort,
+    ) -> Result<Self::Point, Error>;
+
+    /// Extracts the affine x-coordinate of a point.
+    fn extract_p(point: &Self::Point) -> Self::X;
+
+    /// Returns the chip's pre-loaded windowed lookup table for the given
+    /// fixed base, for use in [`EccInstructions::mul_fixed`].
+    fn get_fixed(&self, base: &Self::FixedPoints) -> Self::FixedPoint;
+
+    /// Returns the chip's pre-loaded windowed lookup table for the given
+    /// fixed base, for use in [`EccInstructions::mul_fixed_short`].
+    fn get_fixed_short(&self, base: &Self::FixedPointsShort) -> Self::FixedPointShort;
+}
+
+/// A full-width element of the given elliptic curve's scalar field, for use in
+/// fixed-base scalar multiplication.
+#[derive(Debug)]
+pub struct ScalarFixed<C: CurveAffine, EccChip: EccInstructions<C>> {
+    inner: EccChip::ScalarFixed,
+}
+
+impl<C: CurveAffine, EccChip: EccInstructions<C>> ScalarFixed<C, EccChip> {
+    /// Witnesses the given scalar-field element.
+    pub fn new(
+        mut layouter: impl Layouter<EccChip>,
+        value: Option<C::Scalar>,
+    ) -> Result<Self, Error> {
+        EccChip::witness_scalar_fixed(&mut layouter, value).map(|inner| ScalarFixed { inner })
+    }
+}
+
+/// A signed scalar used in fixed-base scalar multiplication, whose magnitude
+/// fits in 64 bits, together with a sign of `+1` or `-1`.
+#[derive(Debug)]
+pub struct ScalarFixedShort<C: CurveAffine, EccChip: EccInstructions<C>> {
+    inner: EccChip::ScalarFixedShort,
+}
+
+impl<C: CurveAffine, EccChip: EccInstructions<C>> ScalarFixedShort<C, EccChip> {
+    /// Witnesses the given scalar-field element, representing a signed value
+    /// in the range `[-(2^64 - 1), 2^64 - 1]`.
+    pub fn new(
+        mut layouter: impl Layouter<EccChip>,
+        value: Option<C::Scalar>,
+    ) -> Result<Self, Error> {
+        EccChip::witness_scalar_fixed_short(&mut layouter, value)
+            .map(|inner| ScalarFixedShort { inner })
+    }
 }
 
-/// An element of the given elliptic curve's scalar field.
+/// An element of the given elliptic curve's base field, for use in
+/// variable-base scalar multiplication.
 #[derive(Debug)]
-pub struct Scalar<C: CurveAffine, EccChip: EccInstructions<C>> {
-    inner: EccChip::Scalar,
+pub struct ScalarVar<C: CurveAffine, EccChip: EccInstructions<C>> {
+    inner: EccChip::ScalarVar,
+}
+
+impl<C: CurveAffine, EccChip: EccInstructions<C>> ScalarVar<C, EccChip> {
+    /// Witnesses the given base-field element.
+    pub fn new(
+        mut layouter: impl Layouter<EccChip>,
+        value: Option<C::Base>,
+    ) -> Result<Self, Error> {
+        EccChip::witness_scalar_var(&mut layouter, value).map(|inner| ScalarVar { inner })
+    }
 }
 
 /// An elliptic curve point over the given curve.
@@ -54,12 +200,41 @@ pub struct Point<C: CurveAffine, EccChip: EccInstructions<C>> {
     inner: EccChip::Point,
 }
 
+/// The affine x-coordinate of an elliptic curve point over the given curve.
+#[derive(Debug)]
+pub struct X<C: CurveAffine, EccChip: EccInstructions<C>> {
+    inner: EccChip::X,
+}
+
 impl<C: CurveAffine, EccChip: EccInstructions<C>> Point<C, EccChip> {
-    /// Returns `self + other`.
+    /// Witnesses the given affine point.
+    pub fn new(mut layouter: impl Layouter<EccChip>, value: Option<C>) -> Result<Self, Error> {
+        EccChip::witness_point(&mut layouter, value).map(|inner| Point { inner })
+    }
+
+    /// Returns the affine x-coordinate of this point.
+    pub fn x(&self) -> X<C, EccChip> {
+        X {
+            inner: EccChip::extract_p(&self.inner),
+        }
+    }
+
+    /// Returns `self + other`, using complete point addition.
     pub fn add(&self, mut layouter: impl Layouter<EccChip>, other: &Self) -> Result<Self, Error> {
         EccChip::add(&mut layouter, &self.inner, &other.inner).map(|inner| Point { inner })
     }
 
+    /// Returns `self + other`, using incomplete point addition. The caller
+    /// must ensure that `self` and `other` are distinct, non-identity points.
+    pub fn add_incomplete(
+        &self,
+        mut layouter: impl Layouter<EccChip>,
+        other: &Self,
+    ) -> Result<Self, Error> {
+        EccChip::add_incomplete(&mut layouter, &self.inner, &other.inner)
+            .map(|inner| Point { inner })
+    }
+
     /// Returns `[2] self`.
     pub fn double(&self, mut layouter: impl Layouter<EccChip>) -> Result<Self, Error> {
         EccChip::double(&mut layouter, &self.inner).map(|inner| Point { inner })
@@ -69,26 +244,64 @@ impl<C: CurveAffine, EccChip: EccInstructions<C>> Point<C, EccChip> {
     pub fn mul(
         &self,
         mut layouter: impl Layouter<EccChip>,
-        by: &Scalar<C, EccChip>,
+        by: &ScalarVar<C, EccChip>,
     ) -> Result<Self, Error> {
         EccChip::mul(&mut layouter, &by.inner, &self.inner).map(|inner| Point { inner })
     }
 }
 
-/// A constant elliptic curve point over the given curve, for which scalar multiplication
-/// is more efficient.
+/// A constant elliptic curve point over the given curve, selected from the
+/// chip's pre-loaded fixed bases, for which scalar multiplication is more
+/// efficient.
 #[derive(Debug)]
 pub struct FixedPoint<C: CurveAffine, EccChip: EccInstructions<C>> {
     inner: EccChip::FixedPoint,
 }
 
 impl<C: CurveAffine, EccChip: EccInstructions<C>> FixedPoint<C, EccChip> {
+    /// Selects the chip's pre-loaded windowed lookup table for the given
+    /// fixed base. Call sites that share a generator select the same
+    /// `base` and thus reuse the same loaded table.
+    pub fn from_inner(chip: &EccChip, base: EccChip::FixedPoints) -> Self {
+        FixedPoint {
+            inner: chip.get_fixed(&base),
+        }
+    }
+
     /// Returns `[by] self`.
     pub fn mul(
         &self,
         mut layouter: impl Layouter<EccChip>,
-        by: &Scalar<C, EccChip>,
+        by: &ScalarFixed<C, EccChip>,
     ) -> Result<Point<C, EccChip>, Error> {
         EccChip::mul_fixed(&mut layouter, &by.inner, &self.inner).map(|inner| Point { inner })
     }
 }
+
+/// A constant elliptic curve point over the given curve, selected from the
+/// chip's pre-loaded short fixed bases, used in fixed-base scalar
+/// multiplication by a signed scalar of at most 64 bits in magnitude.
+#[derive(Debug)]
+pub struct FixedPointShort<C: CurveAffine, EccChip: EccInstructions<C>> {
+    inner: EccChip::FixedPointShort,
+}
+
+impl<C: CurveAffine, EccChip: EccInstructions<C>> FixedPointShort<C, EccChip> {
+    /// Selects the chip's pre-loaded windowed lookup table for the given
+    /// short fixed base. Call sites that share a generator select the same
+    /// `base` and thus reuse the same loaded table.
+    pub fn from_inner(chip: &EccChip, base: EccChip::FixedPointsShort) -> Self {
+        FixedPointShort {
+            inner: chip.get_fixed_short(&base),
+        }
+    }
+
+    /// Returns `[by] self`.
+    pub fn mul(
+        &self,
+        mut layouter: impl Layouter<EccChip>,
+        by: &ScalarFixedShort<C, EccChip>,
+    ) -> Result<Point<C, EccChip>, Error> {
+        EccChip::mul_fixed_short(&mut layouter, &by.inner, &self.inner).map(|inner| Point { inner })
+    }
+}